@@ -1,5 +1,9 @@
 //! Terminal [`Styles`] for help and error output
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+
 pub use anstyle::*;
 
 /// Terminal styling definitions
@@ -38,6 +42,7 @@ pub struct Styles {
     context_env_data: Option<Style>,
     context_possible_values: Option<Style>,
     context_possible_values_data: Option<Style>,
+    named: Option<HashMap<Cow<'static, str>, Style>>,
 }
 
 impl Styles {
@@ -61,6 +66,7 @@ impl Styles {
             context_env_data: None,
             context_possible_values: None,
             context_possible_values_data: None,
+            named: None,
         }
     }
 
@@ -88,6 +94,7 @@ impl Styles {
                 context_env_data: None,
                 context_possible_values: None,
                 context_possible_values_data: None,
+                named: None,
             }
         }
         #[cfg(not(feature = "color"))]
@@ -96,6 +103,46 @@ impl Styles {
         }
     }
 
+    /// Force the full [`Styles::styled`] palette, regardless of the current environment
+    ///
+    /// See also [`Styles::never`] and [`Styles::auto`].
+    pub const fn always() -> Self {
+        Self::styled()
+    }
+
+    /// Force no styling, regardless of the current environment
+    ///
+    /// An alias of [`Styles::plain`].
+    ///
+    /// See also [`Styles::always`] and [`Styles::auto`].
+    pub const fn never() -> Self {
+        Self::plain()
+    }
+
+    /// Resolve to [`Styles::styled`] or [`Styles::plain`] depending on whether color output is
+    /// appropriate for the current environment
+    ///
+    /// Honors the conventional precedence a user expects: `NO_COLOR` set ⇒ plain;
+    /// `CLICOLOR_FORCE` set and non-empty/non-`0` ⇒ styled; otherwise styled only when stdout is
+    /// a terminal. This is the three-mode palette pattern `snapbox` uses (`always`/`never`/`auto`),
+    /// so a single `Command::styles(Styles::auto())` call behaves correctly across pipes,
+    /// redirects, and CI.
+    pub fn auto() -> Self {
+        use std::io::IsTerminal;
+
+        if std::env::var_os("NO_COLOR").is_some() {
+            return Self::plain();
+        }
+        if std::env::var_os("CLICOLOR_FORCE").is_some_and(|val| !val.is_empty() && val != "0") {
+            return Self::styled();
+        }
+        if std::io::stdout().is_terminal() {
+            Self::styled()
+        } else {
+            Self::plain()
+        }
+    }
+
     /// General Heading style, e.g. [`help_heading`][crate::Arg::help_heading]
     #[inline]
     pub const fn header(mut self, style: Style) -> Self {
@@ -406,6 +453,298 @@ impl Styles {
     }
 }
 
+/// Parsing
+impl Styles {
+    /// Build a [`Styles`] from slot-name -> spec-string pairs, e.g. `("error", "red bold")`.
+    ///
+    /// Recognized slot names match the setters above (`header`, `error`, `usage`, `literal`,
+    /// `placeholder`, `valid`, `invalid`, `context`, `context_data`, `context_aliases`, ...).
+    /// Unrecognized slot names are ignored. Slots that aren't present keep their
+    /// [`Styles::styled`] default, mirroring the existing `get_*` resolution chain.
+    ///
+    /// See [`parse_style`] for the spec-string format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use clap_builder as clap;
+    /// # use clap::builder::styling::Styles;
+    /// let styles = Styles::from_slots([("header", "yellow bold"), ("error", "red bold")])
+    ///     .unwrap();
+    /// ```
+    pub fn from_slots<'s>(
+        slots: impl IntoIterator<Item = (&'s str, &'s str)>,
+    ) -> Result<Self, StyleParseError> {
+        let mut styles = Self::default();
+        for (slot, spec) in slots {
+            let style = parse_style(spec)?;
+            match slot {
+                "header" => styles.header = style,
+                "error" => styles.error = style,
+                "usage" => styles.usage = style,
+                "literal" => styles.literal = style,
+                "placeholder" => styles.placeholder = style,
+                "valid" => styles.valid = style,
+                "invalid" => styles.invalid = style,
+                "context" => styles.context = style,
+                "context_data" => styles.context_data = Some(style),
+                "context_aliases" => styles.context_aliases = Some(style),
+                "context_aliases_data" => styles.context_aliases_data = Some(style),
+                "context_default" => styles.context_default = Some(style),
+                "context_default_data" => styles.context_default_data = Some(style),
+                "context_env" => styles.context_env = Some(style),
+                "context_env_data" => styles.context_env_data = Some(style),
+                "context_possible_values" => styles.context_possible_values = Some(style),
+                "context_possible_values_data" => styles.context_possible_values_data = Some(style),
+                _ => {}
+            }
+        }
+        Ok(styles)
+    }
+}
+
+/// Parse a human-readable effect spec, e.g. `"red bold underline"` or `"brightcyan on_black dim"`,
+/// into a [`Style`].
+///
+/// Tokens are split on whitespace and commas. Each token is one of:
+/// * a base color name (`black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white`)
+///   or its `bright*` variant (e.g. `brightcyan`), setting the foreground color
+/// * the same names prefixed with `on_` (e.g. `on_black`), setting the background color
+/// * `bold`, `dim`, `italic`, `underline`, or `inverse`/`reverse`, setting the matching [`Effects`]
+///
+/// An unrecognized token is a [`StyleParseError`] naming the offending token. This mirrors how
+/// Mercurial maps semantic names (e.g. `grep.match => [red, bold]`) onto terminal effects.
+pub fn parse_style(spec: &str) -> Result<Style, StyleParseError> {
+    let mut style = Style::new();
+    for token in spec
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+    {
+        let lower = token.to_ascii_lowercase();
+        match lower.as_str() {
+            "bold" => style = style.bold(),
+            "dim" => style = style.dimmed(),
+            "italic" => style = style.italic(),
+            "underline" => style = style.underline(),
+            "inverse" | "reverse" => style = style.invert(),
+            _ => {
+                if let Some(name) = lower.strip_prefix("on_") {
+                    let color = parse_color_name(name).ok_or_else(|| StyleParseError::new(token))?;
+                    style = style.bg_color(Some(Color::Ansi(color)));
+                } else if let Some(color) = parse_color_name(&lower) {
+                    style = style.fg_color(Some(Color::Ansi(color)));
+                } else {
+                    return Err(StyleParseError::new(token));
+                }
+            }
+        }
+    }
+    Ok(style)
+}
+
+fn parse_color_name(name: &str) -> Option<AnsiColor> {
+    Some(match name {
+        "black" => AnsiColor::Black,
+        "red" => AnsiColor::Red,
+        "green" => AnsiColor::Green,
+        "yellow" => AnsiColor::Yellow,
+        "blue" => AnsiColor::Blue,
+        "magenta" => AnsiColor::Magenta,
+        "cyan" => AnsiColor::Cyan,
+        "white" => AnsiColor::White,
+        "brightblack" => AnsiColor::BrightBlack,
+        "brightred" => AnsiColor::BrightRed,
+        "brightgreen" => AnsiColor::BrightGreen,
+        "brightyellow" => AnsiColor::BrightYellow,
+        "brightblue" => AnsiColor::BrightBlue,
+        "brightmagenta" => AnsiColor::BrightMagenta,
+        "brightcyan" => AnsiColor::BrightCyan,
+        "brightwhite" => AnsiColor::BrightWhite,
+        _ => return None,
+    })
+}
+
+/// Error parsing a style spec string, see [`parse_style`] and [`Styles::from_slots`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleParseError {
+    token: String,
+}
+
+impl StyleParseError {
+    fn new(token: &str) -> Self {
+        Self {
+            token: token.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for StyleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unrecognized style token `{}`", self.token)
+    }
+}
+
+impl std::error::Error for StyleParseError {}
+
+/// Named slots
+///
+/// The fixed slots above can't style custom [`help_heading`][crate::Arg::help_heading] sections
+/// or app-specific markup. This registry lets a help template or a custom section name resolve
+/// to a user-provided style, following Mercurial's approach of keying effects off arbitrary
+/// semantic labels (its `EffectsMap` is `HashMap<Vec<u8>, Vec<Effect>>` with entries like
+/// `branches.current`). The typed slots above remain the fast path; this is only consulted for
+/// names that aren't built in.
+impl Styles {
+    /// Register (or override) the style for an arbitrary, non-built-in slot name
+    #[inline]
+    pub fn with_named(mut self, name: impl Into<Cow<'static, str>>, style: Style) -> Self {
+        self.named
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), style);
+        self
+    }
+
+    /// Look up the style registered for `name` via [`Styles::with_named`]
+    ///
+    /// Returns `None` when `name` hasn't been registered; callers typically fall back to
+    /// [`Styles::get_header`] or [`Styles::get_context`] in that case.
+    #[inline]
+    pub fn get_named(&self, name: &str) -> Option<&Style> {
+        self.named.as_ref()?.get(name)
+    }
+}
+
+#[cfg(test)]
+mod named_slot_tests {
+    use super::*;
+
+    #[test]
+    fn unregistered_name_is_none() {
+        let styles = Styles::plain();
+        assert!(styles.get_named("custom").is_none());
+    }
+
+    #[test]
+    fn registered_name_round_trips() {
+        let style = Style::new().bold();
+        let styles = Styles::plain().with_named("custom", style);
+        assert_eq!(styles.get_named("custom"), Some(&style));
+        assert!(styles.get_named("other").is_none());
+    }
+
+    #[test]
+    fn with_named_overrides_existing_entry() {
+        let styles = Styles::plain()
+            .with_named("custom", Style::new().bold())
+            .with_named("custom", Style::new().underline());
+        assert_eq!(styles.get_named("custom"), Some(&Style::new().underline()));
+    }
+}
+
+#[cfg(test)]
+mod parse_style_tests {
+    use super::*;
+
+    #[test]
+    fn parses_color_and_effects() {
+        let style = parse_style("red bold underline").unwrap();
+        assert_eq!(style.get_fg_color(), Some(Color::Ansi(AnsiColor::Red)));
+        assert!(style.get_effects().contains(Effects::BOLD));
+        assert!(style.get_effects().contains(Effects::UNDERLINE));
+    }
+
+    #[test]
+    fn parses_background_color() {
+        let style = parse_style("on_black").unwrap();
+        assert_eq!(style.get_bg_color(), Some(Color::Ansi(AnsiColor::Black)));
+    }
+
+    #[test]
+    fn is_case_insensitive() {
+        let style = parse_style("BrightCyan BOLD").unwrap();
+        assert_eq!(
+            style.get_fg_color(),
+            Some(Color::Ansi(AnsiColor::BrightCyan))
+        );
+        assert!(style.get_effects().contains(Effects::BOLD));
+    }
+
+    #[test]
+    fn accepts_comma_separated_tokens() {
+        let style = parse_style("red, bold").unwrap();
+        assert_eq!(style.get_fg_color(), Some(Color::Ansi(AnsiColor::Red)));
+        assert!(style.get_effects().contains(Effects::BOLD));
+    }
+
+    #[test]
+    fn rejects_unrecognized_token() {
+        let err = parse_style("not_a_real_token").unwrap_err();
+        assert_eq!(err.to_string(), "unrecognized style token `not_a_real_token`");
+    }
+
+    #[test]
+    fn empty_spec_is_plain() {
+        assert_eq!(parse_style("").unwrap(), Style::new());
+    }
+}
+
+#[cfg(test)]
+mod auto_tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `Styles::auto` reads process-global env vars; serialize the tests that touch them so they
+    // don't stomp on each other when the test binary runs them concurrently.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn with_env<T>(vars: &[(&str, Option<&str>)], f: impl FnOnce() -> T) -> T {
+        let _guard = ENV_LOCK.lock().unwrap();
+        for (key, _) in vars {
+            std::env::remove_var(key);
+        }
+        for (key, value) in vars {
+            if let Some(value) = value {
+                std::env::set_var(key, value);
+            }
+        }
+        let result = f();
+        for (key, _) in vars {
+            std::env::remove_var(key);
+        }
+        result
+    }
+
+    #[test]
+    fn no_color_forces_plain() {
+        with_env(
+            &[("NO_COLOR", Some("1")), ("CLICOLOR_FORCE", Some("1"))],
+            || {
+                assert_eq!(
+                    Styles::auto().get_header().get_effects(),
+                    Styles::plain().get_header().get_effects()
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn clicolor_force_overrides_non_terminal() {
+        with_env(&[("NO_COLOR", None), ("CLICOLOR_FORCE", Some("1"))], || {
+            assert_eq!(
+                Styles::auto().get_header().get_effects(),
+                Styles::styled().get_header().get_effects()
+            );
+        });
+    }
+
+    #[test]
+    fn clicolor_force_empty_is_ignored() {
+        with_env(&[("NO_COLOR", None), ("CLICOLOR_FORCE", Some(""))], || {
+            assert_eq!(Styles::auto().get_header(), Styles::plain().get_header());
+        });
+    }
+}
+
 impl super::AppExt for Styles {}
 
 impl Default for Styles {
@@ -420,3 +759,198 @@ impl Default for &'_ Styles {
         &STYLES
     }
 }
+
+#[cfg(feature = "serde")]
+mod serializing {
+    //! Lets an app load its help/error theme from a config file (TOML/JSON/YAML/...) and pass
+    //! it to [`Command::styles`][crate::Command::styles], the same way tools like starship let
+    //! users override palette entries from a config table.
+    //!
+    //! Each field (de)serializes from the same spec-string format (`"red bold"`) used by
+    //! [`parse_style`]; missing fields fall back to [`Styles::styled`] defaults exactly as the
+    //! `get_*` resolution chain does.
+    use super::{parse_style, AnsiColor, Color, Effects, Style, Styles};
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Default, Deserialize, Serialize)]
+    #[serde(rename_all = "snake_case")]
+    struct StylesSpec {
+        header: Option<String>,
+        error: Option<String>,
+        usage: Option<String>,
+        literal: Option<String>,
+        placeholder: Option<String>,
+        valid: Option<String>,
+        invalid: Option<String>,
+        context: Option<String>,
+        context_data: Option<String>,
+        context_aliases: Option<String>,
+        context_aliases_data: Option<String>,
+        context_default: Option<String>,
+        context_default_data: Option<String>,
+        context_env: Option<String>,
+        context_env_data: Option<String>,
+        context_possible_values: Option<String>,
+        context_possible_values_data: Option<String>,
+    }
+
+    impl Serialize for Styles {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let spec = StylesSpec {
+                header: Some(style_to_spec(&self.header)),
+                error: Some(style_to_spec(&self.error)),
+                usage: Some(style_to_spec(&self.usage)),
+                literal: Some(style_to_spec(&self.literal)),
+                placeholder: Some(style_to_spec(&self.placeholder)),
+                valid: Some(style_to_spec(&self.valid)),
+                invalid: Some(style_to_spec(&self.invalid)),
+                context: Some(style_to_spec(&self.context)),
+                context_data: self.context_data.as_ref().map(style_to_spec),
+                context_aliases: self.context_aliases.as_ref().map(style_to_spec),
+                context_aliases_data: self.context_aliases_data.as_ref().map(style_to_spec),
+                context_default: self.context_default.as_ref().map(style_to_spec),
+                context_default_data: self.context_default_data.as_ref().map(style_to_spec),
+                context_env: self.context_env.as_ref().map(style_to_spec),
+                context_env_data: self.context_env_data.as_ref().map(style_to_spec),
+                context_possible_values: self.context_possible_values.as_ref().map(style_to_spec),
+                context_possible_values_data: self
+                    .context_possible_values_data
+                    .as_ref()
+                    .map(style_to_spec),
+            };
+            spec.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Styles {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let spec = StylesSpec::deserialize(deserializer)?;
+            let mut styles = Styles::default();
+            macro_rules! apply {
+                ($field:ident => $target:expr) => {
+                    if let Some(raw) = spec.$field {
+                        $target = parse_style(&raw).map_err(D::Error::custom)?;
+                    }
+                };
+            }
+            apply!(header => styles.header);
+            apply!(error => styles.error);
+            apply!(usage => styles.usage);
+            apply!(literal => styles.literal);
+            apply!(placeholder => styles.placeholder);
+            apply!(valid => styles.valid);
+            apply!(invalid => styles.invalid);
+            apply!(context => styles.context);
+            if let Some(raw) = spec.context_data {
+                styles.context_data = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_aliases {
+                styles.context_aliases = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_aliases_data {
+                styles.context_aliases_data = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_default {
+                styles.context_default = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_default_data {
+                styles.context_default_data = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_env {
+                styles.context_env = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_env_data {
+                styles.context_env_data = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_possible_values {
+                styles.context_possible_values = Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            if let Some(raw) = spec.context_possible_values_data {
+                styles.context_possible_values_data =
+                    Some(parse_style(&raw).map_err(D::Error::custom)?);
+            }
+            Ok(styles)
+        }
+    }
+
+    fn style_to_spec(style: &Style) -> String {
+        let mut tokens = Vec::new();
+        if let Some(Color::Ansi(color)) = style.get_fg_color() {
+            tokens.push(color_name(color).to_owned());
+        }
+        if let Some(Color::Ansi(color)) = style.get_bg_color() {
+            tokens.push(format!("on_{}", color_name(color)));
+        }
+        let effects = style.get_effects();
+        if effects.contains(Effects::BOLD) {
+            tokens.push("bold".to_owned());
+        }
+        if effects.contains(Effects::DIMMED) {
+            tokens.push("dim".to_owned());
+        }
+        if effects.contains(Effects::ITALIC) {
+            tokens.push("italic".to_owned());
+        }
+        if effects.contains(Effects::UNDERLINE) {
+            tokens.push("underline".to_owned());
+        }
+        if effects.contains(Effects::INVERT) {
+            tokens.push("inverse".to_owned());
+        }
+        tokens.join(" ")
+    }
+
+    fn color_name(color: AnsiColor) -> &'static str {
+        match color {
+            AnsiColor::Black => "black",
+            AnsiColor::Red => "red",
+            AnsiColor::Green => "green",
+            AnsiColor::Yellow => "yellow",
+            AnsiColor::Blue => "blue",
+            AnsiColor::Magenta => "magenta",
+            AnsiColor::Cyan => "cyan",
+            AnsiColor::White => "white",
+            AnsiColor::BrightBlack => "brightblack",
+            AnsiColor::BrightRed => "brightred",
+            AnsiColor::BrightGreen => "brightgreen",
+            AnsiColor::BrightYellow => "brightyellow",
+            AnsiColor::BrightBlue => "brightblue",
+            AnsiColor::BrightMagenta => "brightmagenta",
+            AnsiColor::BrightCyan => "brightcyan",
+            AnsiColor::BrightWhite => "brightwhite",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::{parse_style, Styles};
+        use super::{AnsiColor, Color};
+
+        #[test]
+        fn round_trips_through_json() {
+            let styles = Styles::plain()
+                .header(parse_style("yellow bold").unwrap())
+                .error(parse_style("red bold").unwrap());
+            let json = serde_json::to_string(&styles).unwrap();
+            let restored: Styles = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                restored.get_header().get_fg_color(),
+                Some(Color::Ansi(AnsiColor::Yellow))
+            );
+            assert_eq!(
+                restored.get_error().get_fg_color(),
+                Some(Color::Ansi(AnsiColor::Red))
+            );
+        }
+
+        #[test]
+        fn missing_fields_fall_back_to_styled_defaults() {
+            let restored: Styles = serde_json::from_str("{}").unwrap();
+            assert_eq!(
+                restored.get_header().get_fg_color(),
+                Styles::styled().get_header().get_fg_color()
+            );
+        }
+    }
+}
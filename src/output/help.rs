@@ -2,7 +2,8 @@
 use std::borrow::Cow;
 use std::cmp;
 use std::collections::BTreeMap;
-use std::io::{self, Cursor, Read, Write};
+use std::io::{self, Write};
+use std::mem;
 use std::usize;
 
 // Internal
@@ -25,9 +26,78 @@ mod term_size {
     pub fn dimensions() -> Option<(usize, usize)> { None }
 }
 
-fn str_width(s: &str) -> usize { UnicodeWidthStr::width(s) }
+/// Measures the display width of `s` in terminal columns via [`UnicodeWidthStr`], so wide CJK
+/// ideographs and emoji are counted as two columns and combining marks as zero.
+fn str_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Identifies one of the built-in, automatically generated sections of `{all-args}`/
+/// [`Help::write_all_args`], for ordering via [`HelpLayout::section_order`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HelpSection {
+    /// Positional arguments (`ARGS:` by default).
+    Positionals,
+    /// Flags and options (`FLAGS:`/`OPTIONS:`, or the unified `OPTIONS:` block, by default).
+    Options,
+    /// Subcommands (`SUBCOMMANDS:` by default).
+    Subcommands,
+}
 
-const TAB: &'static str = "    ";
+/// Tunable indentation, column-alignment, and section-heading parameters for [`Help`].
+///
+/// Replaces the previously hardcoded tab width (`TAB`), the `+4`/`+8`/`+12` alignment gutters,
+/// the `0.40` next-line threshold, and the hardcoded `ARGS:`/`FLAGS:`/`OPTIONS:`/`SUBCOMMANDS:`
+/// section headings and their fixed order, so apps can adapt generated help to their own house
+/// style, to very wide or very narrow terminals, and to localized or restyled output.
+#[derive(Clone, Debug)]
+pub struct HelpLayout {
+    /// Width, in spaces, of a single indentation level.
+    pub tab_width: usize,
+    /// Number of `tab_width`-wide indents reserved as a gutter before a right-aligned help column.
+    pub help_gutter: usize,
+    /// Terminal-width fraction beyond which help is forced onto its own line.
+    pub next_line_threshold: f32,
+    /// Heading line printed above the positional-arguments section.
+    pub args_heading: Cow<'static, str>,
+    /// Heading line printed above the flags section (non-unified help only).
+    pub flags_heading: Cow<'static, str>,
+    /// Heading line printed above the options section (also used for the unified block).
+    pub options_heading: Cow<'static, str>,
+    /// Heading line printed above the subcommands section.
+    pub subcommands_heading: Cow<'static, str>,
+    /// Top-to-bottom emission order of the built-in sections in [`Help::write_all_args`].
+    pub section_order: [HelpSection; 3],
+}
+
+impl HelpLayout {
+    fn tab(&self) -> String {
+        " ".repeat(self.tab_width)
+    }
+
+    fn gutter(&self) -> usize {
+        self.tab_width * self.help_gutter
+    }
+}
+
+impl Default for HelpLayout {
+    fn default() -> Self {
+        HelpLayout {
+            tab_width: 4,
+            help_gutter: 3,
+            next_line_threshold: 0.40,
+            args_heading: Cow::Borrowed("ARGS:\n"),
+            flags_heading: Cow::Borrowed("FLAGS:\n"),
+            options_heading: Cow::Borrowed("OPTIONS:\n"),
+            subcommands_heading: Cow::Borrowed("SUBCOMMANDS:\n"),
+            section_order: [
+                HelpSection::Positionals,
+                HelpSection::Options,
+                HelpSection::Subcommands,
+            ],
+        }
+    }
+}
 
 macro_rules! color {
     ($_self:ident, $s:expr, $c:ident) => {
@@ -59,11 +129,13 @@ pub struct Help<'w> {
     longest: usize,
     force_next_line: bool,
     use_long: bool,
+    layout: HelpLayout,
+    hyperlinks: bool,
 }
 
 // Public Functions
 impl<'w> Help<'w> {
-    /// Create a new `Help` instance.
+    /// Create a new `Help` instance, using the default `HelpLayout`.
     #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
     pub fn new(
         w: &'w mut Write,
@@ -76,6 +148,33 @@ impl<'w> Help<'w> {
         use_long: bool,
     ) -> Self {
         debugln!("Help::new;");
+        Self::with_layout(
+            w,
+            next_line_help,
+            hide_pv,
+            color,
+            cizer,
+            term_w,
+            max_w,
+            use_long,
+            HelpLayout::default(),
+        )
+    }
+
+    /// Create a new `Help` instance with a custom `HelpLayout`.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn with_layout(
+        w: &'w mut Write,
+        next_line_help: bool,
+        hide_pv: bool,
+        color: bool,
+        cizer: Colorizer,
+        term_w: Option<usize>,
+        max_w: Option<usize>,
+        use_long: bool,
+        layout: HelpLayout,
+    ) -> Self {
+        debugln!("Help::with_layout;");
         Help {
             writer: w,
             next_line_help: next_line_help,
@@ -99,9 +198,25 @@ impl<'w> Help<'w> {
             longest: 0,
             force_next_line: false,
             use_long: use_long,
+            layout: layout,
+            hyperlinks: false,
         }
     }
 
+    /// Enables OSC 8 terminal hyperlinks for URLs found in help text and possible-values.
+    ///
+    /// Links are emitted only when colored help is also enabled for this `Help` (its `color`
+    /// flag); the output stream itself is not checked for being a terminal or for link support.
+    ///
+    /// Off by default. `_write_parser_help` (backing `App`'s normal `--help`/`-h` output) reads
+    /// this from `App::hyperlinks`, the same way it reads `app.term_w`/`app.max_w`; set it via
+    /// the app's `hyperlinks(true)` builder method. Call this setter directly only when
+    /// hand-building a `Help` without going through `App`/`Parser`.
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
     /// Reads help settings from a Parser
     /// and write its help to the wrapped stream.
     pub fn write_parser_help(w: &'w mut Write, parser: &Parser, use_long: bool) -> ClapResult<()> {
@@ -141,7 +256,8 @@ impl<'w> Help<'w> {
             parser.app.term_w,
             parser.app.max_w,
             use_long,
-        ).write_help(parser)
+        ).with_hyperlinks(parser.app.hyperlinks)
+            .write_help(parser)
     }
 
     /// Writes the parser help to the wrapped stream.
@@ -245,11 +361,11 @@ impl<'w> Help<'w> {
     /// Writes argument's short command to the wrapped stream.
     fn short<'b, 'c>(&mut self, arg: &Arg<'b, 'c>) -> io::Result<()> {
         debugln!("Help::short;");
-        write!(self.writer, "{}", TAB)?;
+        write!(self.writer, "{}", self.layout.tab())?;
         if let Some(s) = arg.short {
             color!(self, "-{}", s, good)
         } else if arg.has_switch() {
-            write!(self.writer, "{}", TAB)
+            write!(self.writer, "{}", self.layout.tab())
         } else {
             Ok(())
         }
@@ -332,10 +448,10 @@ impl<'w> Help<'w> {
         let h = arg.help.unwrap_or("");
         let h_w = str_width(h) + str_width(&*spec_vals);
         let nlh = self.next_line_help || arg.is_set(ArgSettings::NextLineHelp);
-        let taken = self.longest + 12;
+        let taken = self.longest + self.layout.gutter();
         self.force_next_line = !nlh
             && self.term_w >= taken
-            && (taken as f32 / self.term_w as f32) > 0.40
+            && (taken as f32 / self.term_w as f32) > self.layout.next_line_threshold
             && h_w > (self.term_w - taken);
 
         debug!("Help::val: Has switch...");
@@ -360,11 +476,12 @@ impl<'w> Help<'w> {
                 // Since we're writing spaces from the tab point we first need to know if we
                 // had a long and short, or just short
                 if arg.long.is_some() {
-                    // Only account 4 after the val
-                    spcs += 4;
+                    // Only account one tab_width after the val
+                    spcs += self.layout.tab_width;
                 } else {
-                    // Only account for ', --' + 4 after the val
-                    spcs += 8;
+                    // Account for two tab_widths after the val (', --' plus one tab_width at
+                    // the default tab_width of 4)
+                    spcs += self.layout.tab_width * 2;
                 }
 
                 write_nspaces!(self.writer, spcs);
@@ -375,7 +492,7 @@ impl<'w> Help<'w> {
             sdebugln!("No, and not next_line");
             write_nspaces!(
                 self.writer,
-                self.longest + 4 - (str_width(arg.to_string().as_str()))
+                self.longest + self.layout.tab_width - (str_width(arg.to_string().as_str()))
             );
         } else {
             sdebugln!("No");
@@ -432,16 +549,16 @@ impl<'w> Help<'w> {
         debugln!("Help::help: Next Line...{:?}", nlh);
 
         let spcs = if nlh || self.force_next_line {
-            12 // "tab" * 3
+            self.layout.gutter() // "tab" * help_gutter
         } else {
-            self.longest + 12
+            self.longest + self.layout.gutter()
         };
 
         let too_long = spcs + str_width(h) + str_width(&*spec_vals) >= self.term_w;
 
         // Is help on next line, if so then indent
         if nlh || self.force_next_line {
-            write!(self.writer, "\n{}{}{}", TAB, TAB, TAB)?;
+            write!(self.writer, "\n{0}{0}{0}", self.layout.tab())?;
         }
 
         debug!("Help::help: Too long...");
@@ -457,18 +574,18 @@ impl<'w> Help<'w> {
             sdebugln!("No");
         }
         if let Some(part) = help.lines().next() {
-            write!(self.writer, "{}", part)?;
+            write!(self.writer, "{}", self.linkify(part))?;
         }
         for part in help.lines().skip(1) {
             write!(self.writer, "\n")?;
             if nlh || self.force_next_line {
-                write!(self.writer, "{}{}{}", TAB, TAB, TAB)?;
+                write!(self.writer, "{0}{0}{0}", self.layout.tab())?;
             } else if arg.has_switch() {
-                write_nspaces!(self.writer, self.longest + 12);
+                write_nspaces!(self.writer, self.longest + self.layout.gutter());
             } else {
-                write_nspaces!(self.writer, self.longest + 8);
+                write_nspaces!(self.writer, self.longest + self.layout.tab_width * 2);
             }
-            write!(self.writer, "{}", part)?;
+            write!(self.writer, "{}", self.linkify(part))?;
         }
         if !prevent_nlh && !help.contains('\n') && (nlh || self.force_next_line) {
             write!(self.writer, "\n")?;
@@ -476,6 +593,35 @@ impl<'w> Help<'w> {
         Ok(())
     }
 
+    /// Finds `http://`/`https://` URLs in `text` (e.g. in help strings or
+    /// `[possible values: ...]` entries) and, when hyperlinks and colored help are both enabled,
+    /// wraps each in an OSC 8 escape sequence so capable terminals render it as a clickable link.
+    /// Otherwise returns `text` unchanged.
+    fn linkify(&self, text: &str) -> String {
+        if !self.hyperlinks || !self.color {
+            return text.to_owned();
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        loop {
+            let start = match (rest.find("http://"), rest.find("https://")) {
+                (Some(http), Some(https)) => cmp::min(http, https),
+                (Some(start), None) | (None, Some(start)) => start,
+                (None, None) => break,
+            };
+            out.push_str(&rest[..start]);
+            let url_part = &rest[start..];
+            let end = url_part
+                .find(|c: char| c.is_whitespace() || c == ')' || c == ']' || c == '>')
+                .unwrap_or_else(|| url_part.len());
+            let (url, remainder) = url_part.split_at(end);
+            out.push_str(&osc8_hyperlink(url, url));
+            rest = remainder;
+        }
+        out.push_str(rest);
+        out
+    }
+
     fn spec_vals(&self, a: &Arg) -> String {
         debugln!("Help::spec_vals: a={}", a);
         let mut spec_vals = vec![];
@@ -556,7 +702,7 @@ impl<'w> Help<'w> {
 impl<'w> Help<'w> {
     fn write_subcommand<'a, 'b>(&mut self, app: &App<'a, 'b>) -> io::Result<()> {
         debugln!("Help::write_subcommand;");
-        write!(self.writer, "{}", TAB)?;
+        write!(self.writer, "{}", self.layout.tab())?;
         color!(self, "{}", app.name, good)?;
         let spec_vals = self.sc_val(app)?;
         self.sc_help(app, &*spec_vals)?;
@@ -569,16 +715,16 @@ impl<'w> Help<'w> {
         let h = app.about.unwrap_or("");
         let h_w = str_width(h) + str_width(&*spec_vals);
         let nlh = self.next_line_help;
-        let taken = self.longest + 12;
+        let taken = self.longest + self.layout.gutter();
         self.force_next_line = !nlh
             && self.term_w >= taken
-            && (taken as f32 / self.term_w as f32) > 0.40
+            && (taken as f32 / self.term_w as f32) > self.layout.next_line_threshold
             && h_w > (self.term_w - taken);
 
         if !(nlh || self.force_next_line) {
             write_nspaces!(
                 self.writer,
-                self.longest + 4 - (str_width(app.to_string().as_str()))
+                self.longest + self.layout.tab_width - (str_width(app.to_string().as_str()))
             );
         }
         Ok(spec_vals)
@@ -623,16 +769,16 @@ impl<'w> Help<'w> {
         debugln!("Help::sc_help: Next Line...{:?}", nlh);
 
         let spcs = if nlh || self.force_next_line {
-            12 // "tab" * 3
+            self.layout.gutter() // "tab" * help_gutter
         } else {
-            self.longest + 12
+            self.longest + self.layout.gutter()
         };
 
         let too_long = spcs + str_width(h) + str_width(&*spec_vals) >= self.term_w;
 
         // Is help on next line, if so then indent
         if nlh || self.force_next_line {
-            write!(self.writer, "\n{}{}{}", TAB, TAB, TAB)?;
+            write!(self.writer, "\n{0}{0}{0}", self.layout.tab())?;
         }
 
         debug!("Help::sc_help: Too long...");
@@ -648,16 +794,16 @@ impl<'w> Help<'w> {
             sdebugln!("No");
         }
         if let Some(part) = help.lines().next() {
-            write!(self.writer, "{}", part)?;
+            write!(self.writer, "{}", self.linkify(part))?;
         }
         for part in help.lines().skip(1) {
             write!(self.writer, "\n")?;
             if nlh || self.force_next_line {
-                write!(self.writer, "{}{}{}", TAB, TAB, TAB)?;
+                write!(self.writer, "{0}{0}{0}", self.layout.tab())?;
             } else {
-                write_nspaces!(self.writer, self.longest + 8);
+                write_nspaces!(self.writer, self.longest + self.layout.tab_width * 2);
             }
-            write!(self.writer, "{}", part)?;
+            write!(self.writer, "{}", self.linkify(part))?;
         }
         if !help.contains('\n') && (nlh || self.force_next_line) {
             write!(self.writer, "\n")?;
@@ -670,20 +816,26 @@ impl<'w> Help<'w> {
 impl<'w> Help<'w> {
     /// Writes help for all arguments (options, flags, args, subcommands)
     /// including titles of a Parser Object to the wrapped stream.
+    ///
+    /// The heading labels and the top-to-bottom order of the `ARGS:`/`FLAGS:`/`OPTIONS:`/
+    /// `SUBCOMMANDS:` sections are taken from `self.layout` (see [`HelpLayout::section_order`]),
+    /// defaulting to the labels and order above when unset.
     #[cfg_attr(feature = "lints", allow(useless_let_if_seq))]
     #[cfg_attr(feature = "cargo-clippy", allow(useless_let_if_seq))]
     pub fn write_all_args(&mut self, parser: &Parser) -> ClapResult<()> {
         debugln!("Help::write_all_args;");
-        let flags = parser.has_flags();
+        // Args with a `help_heading` are bucketed into their own titled section below instead
+        // of (also) appearing under the default ARGS:/FLAGS:/OPTIONS: bucket.
+        let flags = flags!(parser.app).any(|arg| arg.help_heading.is_none());
         // Strange filter/count vs fold... https://github.com/rust-lang/rust/issues/33038
         let pos = positionals!(parser.app).fold(0, |acc, arg| {
-            if should_show_arg(self.use_long, arg) {
+            if arg.help_heading.is_none() && should_show_arg(self.use_long, arg) {
                 acc + 1
             } else {
                 acc
             }
         }) > 0;
-        let opts = parser.has_opts();
+        let opts = opts!(parser.app).any(|arg| arg.help_heading.is_none());
         let subcmds = parser.has_visible_subcommands();
 
         let custom_headings = custom_headings!(parser.app).fold(0, |acc, arg| {
@@ -695,71 +847,87 @@ impl<'w> Help<'w> {
         }) > 0;
 
         let mut first = true;
-
-        if pos {
-            if !first {
-                self.writer.write_all(b"\n\n")?;
-            }
-            color!(self, "ARGS:\n", warning)?;
-            self.write_args_unsorted(positionals!(parser.app))?;
-            first = false;
-        }
-
-        let unified_help = parser.is_set(AppSettings::UnifiedHelpMessage);
-
-        if unified_help && (flags || opts) {
-            let opts_flags = args!(parser.app).filter(|a| a.has_switch());
-            if !first {
-                self.writer.write_all(b"\n\n")?;
-            }
-            color!(self, "OPTIONS:\n", warning)?;
-            self.write_args(opts_flags)?;
-            first = false;
-        } else {
-            if flags {
-                if !first {
-                    self.writer.write_all(b"\n\n")?;
+        let section_order = self.layout.section_order;
+
+        for section in &section_order {
+            match *section {
+                HelpSection::Positionals => {
+                    if pos {
+                        if !first {
+                            self.writer.write_all(b"\n\n")?;
+                        }
+                        color!(self, self.layout.args_heading.clone(), warning)?;
+                        self.write_args_unsorted(
+                            positionals!(parser.app).filter(|arg| arg.help_heading.is_none()),
+                        )?;
+                        first = false;
+                    }
                 }
-                color!(self, "FLAGS:\n", warning)?;
-                self.write_args(flags!(parser.app))?;
-                first = false;
-            }
-            if opts {
-                if !first {
-                    self.writer.write_all(b"\n\n")?;
+                HelpSection::Options => {
+                    let unified_help = parser.is_set(AppSettings::UnifiedHelpMessage);
+
+                    if unified_help && (flags || opts) {
+                        let opts_flags = args!(parser.app)
+                            .filter(|a| a.has_switch() && a.help_heading.is_none());
+                        if !first {
+                            self.writer.write_all(b"\n\n")?;
+                        }
+                        color!(self, self.layout.options_heading.clone(), warning)?;
+                        self.write_args(opts_flags)?;
+                        first = false;
+                    } else {
+                        if flags {
+                            if !first {
+                                self.writer.write_all(b"\n\n")?;
+                            }
+                            color!(self, self.layout.flags_heading.clone(), warning)?;
+                            self.write_args(
+                                flags!(parser.app).filter(|arg| arg.help_heading.is_none()),
+                            )?;
+                            first = false;
+                        }
+                        if opts {
+                            if !first {
+                                self.writer.write_all(b"\n\n")?;
+                            }
+                            color!(self, self.layout.options_heading.clone(), warning)?;
+                            self.write_args(
+                                opts!(parser.app).filter(|arg| arg.help_heading.is_none()),
+                            )?;
+                            first = false;
+                        }
+                    }
+                    if custom_headings {
+                        for heading in parser
+                            .app
+                            .help_headings
+                            .iter()
+                            .filter(|heading| heading.is_some())
+                            .map(|heading| heading.unwrap())
+                        {
+                            if !first {
+                                self.writer.write_all(b"\n\n")?;
+                            }
+                            color!(self, format!("{}:\n", heading), warning)?;
+                            self.write_args(custom_headings!(parser.app)
+                                .filter(|a| a.help_heading.unwrap() == heading))?;
+                            first = false
+                        }
+                    }
                 }
-                color!(self, "OPTIONS:\n", warning)?;
-                self.write_args(opts!(parser.app))?;
-                first = false;
-            }
-            if custom_headings {
-                for heading in parser
-                    .app
-                    .help_headings
-                    .iter()
-                    .filter(|heading| heading.is_some())
-                    .map(|heading| heading.unwrap())
-                {
-                    if !first {
-                        self.writer.write_all(b"\n\n")?;
+                HelpSection::Subcommands => {
+                    if subcmds {
+                        if !first {
+                            self.writer.write_all(b"\n\n")?;
+                        }
+                        color!(self, self.layout.subcommands_heading.clone(), warning)?;
+                        self.write_subcommands(&parser.app)?;
+                        first = false;
                     }
-                    color!(self, format!("{}:\n", heading), warning)?;
-                    self.write_args(
-                        custom_headings!(parser.app).filter(|a| a.help_heading.unwrap() == heading),
-                    )?;
-                    first = false
                 }
             }
         }
 
-        if subcmds {
-            if !first {
-                self.writer.write_all(b"\n\n")?;
-            }
-            color!(self, "SUBCOMMANDS:\n", warning)?;
-            self.write_subcommands(&parser.app)?;
-        }
-
         Ok(())
     }
 
@@ -854,7 +1022,7 @@ impl<'w> Help<'w> {
         write!(
             self.writer,
             "\n{}{}\n\n",
-            TAB,
+            self.layout.tab(),
             Usage::new(parser).create_usage_no_title(&[])
         )?;
 
@@ -876,102 +1044,224 @@ impl<'w> Help<'w> {
 
         self.writer.flush().map_err(Error::from)
     }
+
+    /// Writes a structured, JSON description of `parser`'s full argument surface to the
+    /// wrapped stream.
+    ///
+    /// Walks the same `Arg`/`App` data used by `write_arg`, `val`, and `spec_vals` and emits a
+    /// stable document describing every non-`Hidden` flag, option, positional, and subcommand, so tooling
+    /// (shell-completion generators, doc sites, GUIs) can consume a command's surface without
+    /// scraping the colorized text output.
+    pub fn write_json(&mut self, parser: &Parser) -> ClapResult<()> {
+        debugln!("Help::write_json;");
+        write!(self.writer, "{}", app_to_json(parser.app)).map_err(Error::from)?;
+        self.writer.flush().map_err(Error::from)
+    }
+
+    /// Writes a `troff` man page for `parser` to the wrapped stream.
+    ///
+    /// Reuses the same data `write_subcommand`, `sc_spec_vals`, and `spec_vals` already extract
+    /// from `Arg`/`App` to produce a `NAME`/`SYNOPSIS`/`DESCRIPTION`/`OPTIONS` troff document, so
+    /// crates can ship a generated man page from their existing argument definitions instead of
+    /// packagers hand-writing one.
+    pub fn write_man(&mut self, parser: &Parser, section: ManSection) -> ClapResult<()> {
+        debugln!("Help::write_man;");
+        let app = parser.app;
+        let name = app.bin_name.clone().unwrap_or_else(|| app.name.clone());
+
+        writeln!(
+            self.writer,
+            ".TH {} {} \"\" \"{}\" \"\"",
+            troff_escape(&name.to_uppercase()),
+            section.as_num(),
+            troff_escape(app.version.unwrap_or(""))
+        ).map_err(Error::from)?;
+
+        writeln!(self.writer, ".SH NAME").map_err(Error::from)?;
+        if let Some(about) = app.about {
+            writeln!(
+                self.writer,
+                "{} \\- {}",
+                troff_escape(&name),
+                troff_escape(about)
+            ).map_err(Error::from)?;
+        } else {
+            writeln!(self.writer, "{}", troff_escape(&name)).map_err(Error::from)?;
+        }
+
+        writeln!(self.writer, ".SH SYNOPSIS").map_err(Error::from)?;
+        writeln!(
+            self.writer,
+            ".B {}",
+            troff_escape(&Usage::new(parser).create_usage_no_title(&[]))
+        ).map_err(Error::from)?;
+
+        if let Some(long_about) = app.long_about {
+            writeln!(self.writer, ".SH DESCRIPTION").map_err(Error::from)?;
+            writeln!(self.writer, "{}", troff_escape(long_about)).map_err(Error::from)?;
+        }
+
+        if parser.has_flags() || parser.has_opts() || parser.has_positionals() {
+            writeln!(self.writer, ".SH OPTIONS").map_err(Error::from)?;
+            for arg in args!(parser.app).filter(|arg| should_show_arg(false, arg)) {
+                self.write_man_arg(arg)?;
+            }
+        }
+
+        if parser.has_visible_subcommands() {
+            writeln!(self.writer, ".SH SUBCOMMANDS").map_err(Error::from)?;
+            for sc in subcommands!(parser.app).filter(|sc| !sc.is_set(AppSettings::Hidden)) {
+                writeln!(self.writer, ".TP").map_err(Error::from)?;
+                writeln!(self.writer, ".B {}", troff_escape(&sc.name)).map_err(Error::from)?;
+                if let Some(about) = sc.about {
+                    writeln!(self.writer, "{}", troff_escape(about)).map_err(Error::from)?;
+                }
+            }
+        }
+
+        self.writer.flush().map_err(Error::from)
+    }
+
+    /// Writes the `.TP`-formatted entry for a single `Arg` to a man page being written by
+    /// `write_man`.
+    fn write_man_arg(&mut self, arg: &Arg) -> ClapResult<()> {
+        writeln!(self.writer, ".TP").map_err(Error::from)?;
+        writeln!(self.writer, ".B {}", troff_escape(&arg.to_string())).map_err(Error::from)?;
+
+        if let Some(help) = arg.help {
+            writeln!(self.writer, "{}", troff_escape(help)).map_err(Error::from)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which manual section a man page generated by `Help::write_man` belongs to, per the `man(7)`
+/// convention (`1` = general commands, `7` = miscellanea, ...).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManSection {
+    /// General commands (`man 1`), the common case for a CLI binary.
+    General,
+    /// A custom section number, e.g. `7` for miscellaneous information.
+    Num(u8),
+}
+
+impl ManSection {
+    fn as_num(&self) -> u8 {
+        match *self {
+            ManSection::General => 1,
+            ManSection::Num(n) => n,
+        }
+    }
 }
 
-/// Possible results for a copying function that stops when a given
-/// byte was found.
-enum CopyUntilResult {
-    DelimiterFound(usize),
-    DelimiterNotFound(usize),
-    ReaderEmpty,
-    ReadError(io::Error),
-    WriteError(io::Error),
+/// Escapes troff special characters in free-form text written into a man page by
+/// `Help::write_man`: every `\` is doubled and every `-` is written as `\-`.
+fn troff_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('-', "\\-")
 }
 
-/// Copies the contents of a reader into a writer until a delimiter byte is found.
-/// On success, the total number of bytes that were
-/// copied from reader to writer is returned.
-fn copy_until<R: Read, W: Write>(r: &mut R, w: &mut W, delimiter_byte: u8) -> CopyUntilResult {
-    debugln!("copy_until;");
-
-    let mut count = 0;
-    for wb in r.bytes() {
-        match wb {
-            Ok(b) => {
-                if b == delimiter_byte {
-                    return CopyUntilResult::DelimiterFound(count);
+/// A single parsed unit of a help template: either literal text to copy verbatim, or a `{tag}`
+/// (optionally parameterized as `{tag:key=value,key2=value2}`) to dispatch on.
+enum TemplateToken<'t> {
+    Literal(String),
+    Tag {
+        name: &'t str,
+        params: Vec<(&'t str, &'t str)>,
+    },
+}
+
+/// Splits a help template into a sequence of `TemplateToken`s.
+///
+/// `{{` and `}}` escape to literal braces. Otherwise, an unescaped `{` opens a tag that runs
+/// until its matching unescaped `}`, with no limit on tag length (unlike the old fixed-size
+/// scan buffer). The tag body is split on its first `:` into a name and a comma-separated
+/// `key=value` parameter list; a body with no `:` has no parameters. An unterminated `{` (no
+/// matching `}`) is copied through as literal text, same as an unrecognized tag name.
+fn tokenize_template(template: &str) -> Vec<TemplateToken> {
+    let mut tokens = vec![];
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while !rest.is_empty() {
+        if rest.starts_with("{{") {
+            literal.push('{');
+            rest = &rest[2..];
+        } else if rest.starts_with("}}") {
+            literal.push('}');
+            rest = &rest[2..];
+        } else if rest.starts_with('{') {
+            match rest[1..].find('}') {
+                Some(rel_end) => {
+                    if !literal.is_empty() {
+                        tokens.push(TemplateToken::Literal(mem::replace(
+                            &mut literal,
+                            String::new(),
+                        )));
+                    }
+                    let body = &rest[1..1 + rel_end];
+                    let (name, params) = match body.find(':') {
+                        Some(colon) => {
+                            let params = body[colon + 1..]
+                                .split(',')
+                                .filter(|pair| !pair.is_empty())
+                                .map(|pair| match pair.find('=') {
+                                    Some(eq) => (&pair[..eq], &pair[eq + 1..]),
+                                    None => (pair, ""),
+                                })
+                                .collect();
+                            (&body[..colon], params)
+                        }
+                        None => (body, vec![]),
+                    };
+                    tokens.push(TemplateToken::Tag { name, params });
+                    rest = &rest[1 + rel_end + 1..];
                 }
-                match w.write(&[b]) {
-                    Ok(c) => count += c,
-                    Err(e) => return CopyUntilResult::WriteError(e),
+                // No closing brace; the rest of the template is literal.
+                None => {
+                    literal.push_str(rest);
+                    rest = "";
                 }
             }
-            Err(e) => return CopyUntilResult::ReadError(e),
+        } else {
+            let c = rest.chars().next().expect("rest is non-empty");
+            literal.push(c);
+            rest = &rest[c.len_utf8()..];
         }
     }
-    if count > 0 {
-        CopyUntilResult::DelimiterNotFound(count)
-    } else {
-        CopyUntilResult::ReaderEmpty
+    if !literal.is_empty() {
+        tokens.push(TemplateToken::Literal(literal));
     }
+    tokens
 }
 
-/// Copies the contents of a reader into a writer until a {tag} is found,
-/// copying the tag content to a buffer and returning its size.
-/// In addition to errors, there are three possible outputs:
-///   - `None`: The reader was consumed.
-///   - `Some(Ok(0))`: No tag was captured but the reader still contains data.
-///   - `Some(Ok(length>0))`: a tag with `length` was captured to the `tag_buffer`.
-fn copy_and_capture<R: Read, W: Write>(
-    r: &mut R,
-    w: &mut W,
-    tag_buffer: &mut Cursor<Vec<u8>>,
-) -> Option<io::Result<usize>> {
-    use self::CopyUntilResult::*;
-    debugln!("copy_and_capture;");
-
-    // Find the opening byte.
-    match copy_until(r, w, b'{') {
-        // The end of the reader was reached without finding the opening tag.
-        // (either with or without having copied data to the writer)
-        // Return None indicating that we are done.
-        ReaderEmpty | DelimiterNotFound(_) => None,
-
-        // Something went wrong.
-        ReadError(e) | WriteError(e) => Some(Err(e)),
-
-        // The opening byte was found.
-        // (either with or without having copied data to the writer)
-        DelimiterFound(_) => {
-            // Lets reset the buffer first and find out how long it is.
-            tag_buffer.set_position(0);
-            let buffer_size = tag_buffer.get_ref().len();
-
-            // Find the closing byte,limiting the reader to the length of the buffer.
-            let mut rb = r.take(buffer_size as u64);
-            match copy_until(&mut rb, tag_buffer, b'}') {
-                // We were already at the end of the reader.
-                // Return None indicating that we are done.
-                ReaderEmpty => None,
-
-                // The closing tag was found.
-                // Return the tag_length.
-                DelimiterFound(tag_length) => Some(Ok(tag_length)),
-
-                // The end of the reader was found without finding the closing tag.
-                // Write the opening byte and captured text to the writer.
-                // Return 0 indicating that nothing was captured but the reader still contains data.
-                DelimiterNotFound(not_tag_length) => match w.write(b"{") {
-                    Err(e) => Some(Err(e)),
-                    _ => match w.write(&tag_buffer.get_ref()[0..not_tag_length]) {
-                        Err(e) => Some(Err(e)),
-                        _ => Some(Ok(0)),
-                    },
-                },
+/// Options parsed from a tag's `key=value` parameter list, e.g. `{options:heading=false}` or
+/// `{subcommands:indent=2}`.
+struct TagOptions {
+    /// Whether to print the section's colorized heading line. Defaults to `false`, matching
+    /// the pre-existing behavior of these tags when written without a parameter.
+    heading: bool,
+    /// Overrides `HelpLayout::tab_width` for the duration of this section, if set.
+    indent: Option<usize>,
+}
 
-                ReadError(e) | WriteError(e) => Some(Err(e)),
+impl TagOptions {
+    fn from_params(params: &[(&str, &str)]) -> Self {
+        let mut opts = TagOptions {
+            heading: false,
+            indent: None,
+        };
+        for &(key, value) in params {
+            match key {
+                "heading" => opts.heading = value != "false" && value != "0",
+                "indent" => {
+                    if let Ok(n) = value.parse() {
+                        opts.indent = Some(n);
+                    }
+                }
+                _ => {}
             }
         }
+        opts
     }
 }
 
@@ -979,8 +1269,7 @@ fn copy_and_capture<R: Read, W: Write>(
 impl<'w> Help<'w> {
     /// Write help to stream for the parser in the format defined by the template.
     ///
-    /// Tags arg given inside curly brackets:
-    /// Valid tags are:
+    /// Tags are given inside curly brackets. Valid tags are:
     ///     * `{bin}`         - Binary name.
     ///     * `{version}`     - Version number.
     ///     * `{author}`      - Author information.
@@ -992,121 +1281,204 @@ impl<'w> Help<'w> {
     ///     * `{options}`     - Help for options.
     ///     * `{positionals}` - Help for positionals arguments.
     ///     * `{subcommands}` - Help for subcommands.
+    ///     * `{arg:NAME}`    - Help for a single argument named `NAME`.
+    ///     * `{subcommand:NAME}` - Help for a single subcommand named `NAME`.
     ///     * `{after-help}`  - Info to be displayed after the help message.
     ///     * `{before-help}` - Info to be displayed before the help message.
     ///
-    /// The template system is, on purpose, very simple. Therefore the tags have to writen
-    /// in the lowercase and without spacing.
+    /// `{{` and `}}` escape to literal braces. The group tags (`{flags}`, `{options}`,
+    /// `{positionals}`, `{subcommands}`, `{unified}`) accept parameters, e.g.
+    /// `{options:heading=true}` to print the section heading (omitted by default, matching
+    /// plain `{options}`) or `{subcommands:indent=2}` to override the indentation for that
+    /// section. The tags have to be written in lowercase and without spacing.
     fn write_templated_help(&mut self, parser: &Parser, template: &str) -> ClapResult<()> {
         debugln!("Help::write_templated_help;");
-        let mut tmplr = Cursor::new(&template);
-        let mut tag_buf = Cursor::new(vec![0u8; 15]);
-
-        // The strategy is to copy the template from the reader to wrapped stream
-        // until a tag is found. Depending on its value, the appropriate content is copied
-        // to the wrapped stream.
-        // The copy from template is then resumed, repeating this sequence until reading
-        // the complete template.
-
-        loop {
-            let tag_length = match copy_and_capture(&mut tmplr, &mut self.writer, &mut tag_buf) {
-                None => return Ok(()),
-                Some(Err(e)) => return Err(Error::from(e)),
-                Some(Ok(val)) if val > 0 => val,
-                _ => continue,
-            };
-
-            debugln!("Help::write_template_help:iter: tag_buf={};", unsafe {
-                String::from_utf8_unchecked(
-                    tag_buf.get_ref()[0..tag_length]
-                        .iter()
-                        .map(|&i| i)
-                        .collect::<Vec<_>>(),
-                )
-            });
-            match &tag_buf.get_ref()[0..tag_length] {
-                b"?" => {
-                    self.writer.write_all(b"Could not decode tag name")?;
+        for token in tokenize_template(template) {
+            match token {
+                TemplateToken::Literal(lit) => {
+                    write!(self.writer, "{}", lit).map_err(Error::from)?;
                 }
-                b"bin" => {
-                    self.write_bin_name(parser)?;
+                TemplateToken::Tag { name, params } => {
+                    self.write_template_tag(parser, name, &params)?;
                 }
-                b"version" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        parser.app.version.unwrap_or("unknown version")
-                    )?;
-                }
-                b"author" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        parser.app.author.unwrap_or("unknown author")
-                    )?;
-                }
-                b"about" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        parser.app.about.unwrap_or("unknown about")
-                    )?;
-                }
-                b"long-about" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        parser.app.long_about.unwrap_or("unknown about")
-                    )?;
-                }
-                b"usage" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        Usage::new(parser).create_usage_no_title(&[])
-                    )?;
-                }
-                b"all-args" => {
-                    self.write_all_args(parser)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_template_tag(
+        &mut self,
+        parser: &Parser,
+        name: &str,
+        params: &[(&str, &str)],
+    ) -> ClapResult<()> {
+        debugln!("Help::write_template_tag: name={}", name);
+        macro_rules! with_opts {
+            ($opts:ident, $heading:expr, $body:expr) => {{
+                let $opts = TagOptions::from_params(params);
+                let saved_tab_width = self.layout.tab_width;
+                if let Some(indent) = $opts.indent {
+                    self.layout.tab_width = indent;
                 }
-                b"unified" => {
+                // $body contains its own `?`, which would return out of write_template_tag
+                // before the tab_width restore below ran. Run it in a closure instead so the
+                // restore always happens, then propagate any error afterwards.
+                let result: ClapResult<()> = (|| {
+                    if $opts.heading {
+                        color!(self, $heading, warning)?;
+                    }
+                    $body;
+                    Ok(())
+                })();
+                self.layout.tab_width = saved_tab_width;
+                result?;
+            }};
+        }
+        match name {
+            "?" => {
+                self.writer.write_all(b"Could not decode tag name")?;
+            }
+            "bin" => {
+                self.write_bin_name(parser)?;
+            }
+            "version" => {
+                write!(
+                    self.writer,
+                    "{}",
+                    parser.app.version.unwrap_or("unknown version")
+                )?;
+            }
+            "author" => {
+                write!(
+                    self.writer,
+                    "{}",
+                    parser.app.author.unwrap_or("unknown author")
+                )?;
+            }
+            "about" => {
+                write!(
+                    self.writer,
+                    "{}",
+                    parser.app.about.unwrap_or("unknown about")
+                )?;
+            }
+            "long-about" => {
+                write!(
+                    self.writer,
+                    "{}",
+                    parser.app.long_about.unwrap_or("unknown about")
+                )?;
+            }
+            "usage" => {
+                write!(
+                    self.writer,
+                    "{}",
+                    Usage::new(parser).create_usage_no_title(&[])
+                )?;
+            }
+            "all-args" => {
+                self.write_all_args(parser)?;
+            }
+            "unified" => {
+                with_opts!(opts, self.layout.options_heading.clone(), {
                     let opts_flags = parser.app.args.values().filter(|a| a.has_switch());
                     self.write_args(opts_flags)?;
+                });
+            }
+            "flags" => {
+                with_opts!(
+                    opts,
+                    self.layout.flags_heading.clone(),
+                    self.write_args(flags!(parser.app))?
+                );
+            }
+            "options" => {
+                with_opts!(
+                    opts,
+                    self.layout.options_heading.clone(),
+                    self.write_args(opts!(parser.app))?
+                );
+            }
+            "positionals" => {
+                with_opts!(
+                    opts,
+                    self.layout.args_heading.clone(),
+                    self.write_args_unsorted(positionals!(parser.app))?
+                );
+            }
+            "subcommands" => {
+                with_opts!(
+                    opts,
+                    self.layout.subcommands_heading.clone(),
+                    self.write_subcommands(parser.app)?
+                );
+            }
+            "arg" => {
+                let arg_name = params.get(0).map(|&(k, _)| k).unwrap_or("");
+                match parser.app.args.values().find(|a| a.name == arg_name) {
+                    Some(arg) => {
+                        // Bypass write_args' should_show_arg filtering: naming an arg
+                        // explicitly should show it even if it's `Hidden`, mirroring how
+                        // `{subcommand:NAME}` below ignores `AppSettings::Hidden`.
+                        self.longest = str_width(arg.to_string().as_str());
+                        self.write_arg(arg, false)?;
+                    }
+                    None => {
+                        write!(self.writer, "Unknown argument `{}`", arg_name)?;
+                    }
                 }
-                b"flags" => {
-                    self.write_args(flags!(parser.app))?;
-                }
-                b"options" => {
-                    self.write_args(opts!(parser.app))?;
-                }
-                b"positionals" => {
-                    self.write_args(positionals!(parser.app))?;
-                }
-                b"subcommands" => {
-                    self.write_subcommands(parser.app)?;
-                }
-                b"after-help" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        parser.app.more_help.unwrap_or("unknown after-help")
-                    )?;
-                }
-                b"before-help" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        parser.app.pre_help.unwrap_or("unknown before-help")
-                    )?;
+            }
+            "subcommand" => {
+                let sc_name = params.get(0).map(|&(k, _)| k).unwrap_or("");
+                match subcommands!(parser.app).find(|s| s.name == sc_name) {
+                    Some(sc) => {
+                        self.longest = str_width(sc.name.as_str());
+                        self.write_subcommand(sc)?;
+                    }
+                    None => {
+                        write!(self.writer, "Unknown subcommand `{}`", sc_name)?;
+                    }
                 }
-                // Unknown tag, write it back.
-                r => {
-                    self.writer.write_all(b"{")?;
-                    self.writer.write_all(r)?;
-                    self.writer.write_all(b"}")?;
+            }
+            "after-help" => {
+                write!(
+                    self.writer,
+                    "{}",
+                    parser.app.more_help.unwrap_or("unknown after-help")
+                )?;
+            }
+            "before-help" => {
+                write!(
+                    self.writer,
+                    "{}",
+                    parser.app.pre_help.unwrap_or("unknown before-help")
+                )?;
+            }
+            // Unknown tag, write it back as `{name}` or `{name:key=value,...}`. Parameters are
+            // re-rendered from their parsed form: an empty value is written as a bare `key`, and
+            // empty parameters are dropped.
+            _ => {
+                self.writer.write_all(b"{")?;
+                write!(self.writer, "{}", name)?;
+                if !params.is_empty() {
+                    write!(self.writer, ":")?;
+                    let rendered = params
+                        .iter()
+                        .map(|(k, v)| {
+                            if v.is_empty() {
+                                (*k).to_owned()
+                            } else {
+                                format!("{}={}", k, v)
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    write!(self.writer, "{}", rendered)?;
                 }
+                self.writer.write_all(b"}")?;
             }
         }
+        Ok(())
     }
 }
 
@@ -1124,6 +1496,149 @@ fn should_show_arg(use_long: bool, arg: &Arg) -> bool {
         || arg.is_set(ArgSettings::NextLineHelp)
 }
 
+/// Wraps `text` in an OSC 8 terminal hyperlink escape sequence pointing at `url`.
+fn osc8_hyperlink(url: &str, text: &str) -> String {
+    format!("\u{1b}]8;;{}\u{1b}\\{}\u{1b}]8;;\u{1b}\\", url, text)
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn json_str(s: &str) -> String {
+    format!("\"{}\"", json_escape(s))
+}
+
+fn json_opt_str(s: Option<&str>) -> String {
+    s.map(json_str).unwrap_or_else(|| "null".to_owned())
+}
+
+/// Builds the JSON object describing a single `Arg`, for `Help::write_json`.
+fn arg_to_json(arg: &Arg) -> String {
+    let mult =
+        arg.is_set(ArgSettings::MultipleValues) || arg.is_set(ArgSettings::MultipleOccurrences);
+
+    let mut fields = vec![
+        format!("\"name\":{}", json_str(arg.name)),
+        format!(
+            "\"short\":{}",
+            arg.short
+                .map(|c| json_str(&c.to_string()))
+                .unwrap_or_else(|| "null".to_owned())
+        ),
+        format!("\"long\":{}", json_opt_str(arg.long)),
+        format!("\"help\":{}", json_opt_str(arg.help)),
+        format!("\"long_help\":{}", json_opt_str(arg.long_help)),
+        format!(
+            "\"default_value\":{}",
+            arg.default_val
+                .map(|v| json_str(&v.to_string_lossy()))
+                .unwrap_or_else(|| "null".to_owned())
+        ),
+        format!(
+            "\"takes_value\":{}",
+            arg.is_set(ArgSettings::TakesValue)
+        ),
+        format!("\"multiple\":{}", mult),
+        format!("\"required\":{}", arg.is_set(ArgSettings::Required)),
+        format!(
+            "\"env\":{}",
+            arg.env
+                .as_ref()
+                .map(|env| json_str(&env.0.to_string_lossy()))
+                .unwrap_or_else(|| "null".to_owned())
+        ),
+    ];
+
+    let aliases = arg
+        .aliases
+        .as_ref()
+        .map(|aliases| {
+            aliases
+                .iter()
+                .filter(|als| als.1)
+                .map(|als| json_str(als.0))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .unwrap_or_default();
+    fields.push(format!("\"aliases\":[{}]", aliases));
+
+    let possible_vals = arg
+        .possible_vals
+        .as_ref()
+        .map(|pv| pv.iter().map(|v| json_str(v)).collect::<Vec<_>>().join(","))
+        .unwrap_or_default();
+    fields.push(format!("\"possible_values\":[{}]", possible_vals));
+
+    let val_names = arg
+        .val_names
+        .as_ref()
+        .map(|names| {
+            names
+                .values()
+                .map(|v| json_str(v))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .unwrap_or_default();
+    fields.push(format!("\"value_names\":[{}]", val_names));
+
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Builds the JSON object describing an `App` (and, recursively, its subcommands), for
+/// `Help::write_json`. `Hidden` args and subcommands are both omitted, matching the rest of the
+/// help output.
+fn app_to_json(app: &App) -> String {
+    let mut fields = vec![
+        format!("\"name\":{}", json_str(&app.name)),
+        format!(
+            "\"bin_name\":{}",
+            app.bin_name
+                .as_ref()
+                .map(|n| json_str(n))
+                .unwrap_or_else(|| "null".to_owned())
+        ),
+        format!("\"version\":{}", json_opt_str(app.version)),
+        format!("\"about\":{}", json_opt_str(app.about)),
+        format!("\"long_about\":{}", json_opt_str(app.long_about)),
+    ];
+
+    let args = args!(app)
+        .filter(|arg| !arg.is_set(ArgSettings::Hidden))
+        .map(|arg| arg_to_json(arg))
+        .collect::<Vec<_>>();
+    fields.push(format!("\"args\":[{}]", args.join(",")));
+
+    let subcommands = subcommands!(app)
+        .filter(|sc| !sc.is_set(AppSettings::Hidden))
+        .map(|sc| app_to_json(sc))
+        .collect::<Vec<_>>();
+    fields.push(format!("\"subcommands\":[{}]", subcommands.join(",")));
+
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Wraps `help` to fit within `avail_chars` columns per line, never splitting a word.
+///
+/// Without the `unicode_help` feature this defers to `textwrap`'s char-counting wrapper, as
+/// before. With it enabled, lines are instead measured with [`str_width`] so that CJK
+/// ideographs, wide emoji, and combining marks wrap at the same column where they're aligned
+/// by [`Help::short`]/[`Help::val`]/[`Help::help`], keeping the help column from drifting.
+#[cfg(not(feature = "unicode_help"))]
 fn wrap_help(help: &str, avail_chars: usize) -> String {
     let wrapper = textwrap::Wrapper::new(avail_chars).break_words(false);
     help.lines()
@@ -1132,13 +1647,241 @@ fn wrap_help(help: &str, avail_chars: usize) -> String {
         .join("\n")
 }
 
+#[cfg(feature = "unicode_help")]
+fn wrap_help(help: &str, avail_chars: usize) -> String {
+    help.lines()
+        .map(|line| wrap_line_by_width(line, avail_chars))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Greedily packs space-separated words from `line` into rows no wider than `avail_chars`
+/// display columns, as measured by [`str_width`]. A single word wider than `avail_chars` is
+/// placed on its own line rather than split, mirroring `textwrap`'s `break_words(false)`.
+#[cfg(feature = "unicode_help")]
+fn wrap_line_by_width(line: &str, avail_chars: usize) -> String {
+    let mut rows = vec![];
+    let mut cur = String::new();
+    let mut cur_w = 0;
+
+    for word in line.split(' ') {
+        let word_w = str_width(word);
+        let next_w = cur_w + if cur.is_empty() { 0 } else { 1 } + word_w;
+        if next_w > avail_chars && !cur.is_empty() {
+            rows.push(mem::replace(&mut cur, String::new()));
+            cur_w = 0;
+        }
+        if !cur.is_empty() {
+            cur.push(' ');
+            cur_w += 1;
+        }
+        cur.push_str(word);
+        cur_w += word_w;
+    }
+    rows.push(cur);
+    rows.join("\n")
+}
+
 #[cfg(test)]
 mod test {
-    use super::wrap_help;
+    use super::{
+        json_escape, json_str, osc8_hyperlink, tokenize_template, troff_escape, wrap_help,
+        HelpLayout, HelpSection, TagOptions, TemplateToken,
+    };
+
+    #[test]
+    fn tag_options_default_has_no_heading_or_indent() {
+        let opts = TagOptions::from_params(&[]);
+        assert_eq!(opts.heading, false);
+        assert_eq!(opts.indent, None);
+    }
+
+    #[test]
+    fn tag_options_parses_heading_and_indent() {
+        let opts = TagOptions::from_params(&[("heading", "true"), ("indent", "2")]);
+        assert_eq!(opts.heading, true);
+        assert_eq!(opts.indent, Some(2));
+    }
+
+    #[test]
+    fn tag_options_heading_false_or_0_disables() {
+        assert_eq!(
+            TagOptions::from_params(&[("heading", "false")]).heading,
+            false
+        );
+        assert_eq!(TagOptions::from_params(&[("heading", "0")]).heading, false);
+    }
+
+    #[test]
+    fn tag_options_ignores_unknown_keys_and_bad_indent() {
+        let opts = TagOptions::from_params(&[("bogus", "1"), ("indent", "not-a-number")]);
+        assert_eq!(opts.heading, false);
+        assert_eq!(opts.indent, None);
+    }
+
+    #[test]
+    fn osc8_hyperlink_wraps_text_in_escape_sequence() {
+        assert_eq!(
+            osc8_hyperlink("https://example.com", "docs"),
+            "\u{1b}]8;;https://example.com\u{1b}\\docs\u{1b}]8;;\u{1b}\\"
+        );
+    }
+
+    #[test]
+    fn help_layout_tab_repeats_spaces_by_tab_width() {
+        let mut layout = HelpLayout::default();
+        layout.tab_width = 2;
+        assert_eq!(layout.tab(), "  ");
+    }
+
+    #[test]
+    fn help_layout_gutter_is_tab_width_times_help_gutter() {
+        let mut layout = HelpLayout::default();
+        layout.tab_width = 4;
+        layout.help_gutter = 3;
+        assert_eq!(layout.gutter(), 12);
+    }
+
+    #[test]
+    fn help_layout_default_section_order_is_positionals_options_subcommands() {
+        assert_eq!(
+            HelpLayout::default().section_order,
+            [
+                HelpSection::Positionals,
+                HelpSection::Options,
+                HelpSection::Subcommands,
+            ]
+        );
+    }
+
+    #[test]
+    fn help_layout_section_order_is_overridable() {
+        let mut layout = HelpLayout::default();
+        layout.section_order = [
+            HelpSection::Subcommands,
+            HelpSection::Positionals,
+            HelpSection::Options,
+        ];
+        assert_eq!(layout.section_order[0], HelpSection::Subcommands);
+    }
+
+    #[test]
+    fn json_escape_control_and_special_chars() {
+        assert_eq!(
+            json_escape("a\"b\\c\nd\re\tf\u{1}"),
+            "a\\\"b\\\\c\\nd\\re\\tf\\u0001"
+        );
+    }
+
+    #[test]
+    fn json_str_wraps_in_quotes() {
+        assert_eq!(json_str("hi \"there\""), "\"hi \\\"there\\\"\"");
+    }
+
+    #[test]
+    fn troff_escape_backslash_and_hyphen() {
+        assert_eq!(troff_escape("a\\b-c"), "a\\\\b\\-c");
+    }
+
+    #[test]
+    fn troff_escape_noop_on_plain_text() {
+        assert_eq!(troff_escape("plain text"), "plain text");
+    }
 
     #[test]
     fn wrap_help_last_word() {
         let help = String::from("foo bar baz");
         assert_eq!(wrap_help(&help, 5), "foo\nbar\nbaz");
     }
+
+    #[cfg(feature = "unicode_help")]
+    #[test]
+    fn wrap_line_by_width_counts_wide_cjk_as_two_columns() {
+        use super::wrap_line_by_width;
+        // Each ideograph "word" is 2 columns wide, so only two fit per 5-column row
+        // (2 + 1 space + 2 = 5).
+        let line = "\u{4e00} \u{4e8c} \u{4e09} \u{56db}";
+        assert_eq!(
+            wrap_line_by_width(line, 5),
+            "\u{4e00} \u{4e8c}\n\u{4e09} \u{56db}"
+        );
+    }
+
+    #[cfg(feature = "unicode_help")]
+    #[test]
+    fn wrap_line_by_width_never_splits_an_overlong_word() {
+        use super::wrap_line_by_width;
+        assert_eq!(wrap_line_by_width("toolongtosplit", 4), "toolongtosplit");
+    }
+
+    #[cfg(feature = "unicode_help")]
+    #[test]
+    fn wrap_line_by_width_packs_ascii_words_greedily() {
+        use super::wrap_line_by_width;
+        assert_eq!(wrap_line_by_width("foo bar baz", 7), "foo bar\nbaz");
+    }
+
+    fn tag_names(tokens: &[TemplateToken]) -> Vec<&str> {
+        tokens
+            .iter()
+            .filter_map(|t| match t {
+                TemplateToken::Tag { name, .. } => Some(*name),
+                TemplateToken::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_template_literal_only() {
+        let tokens = tokenize_template("no tags here");
+        assert_eq!(tokens.len(), 1);
+        match &tokens[0] {
+            TemplateToken::Literal(lit) => assert_eq!(lit, "no tags here"),
+            TemplateToken::Tag { .. } => panic!("expected a literal token"),
+        }
+    }
+
+    #[test]
+    fn tokenize_template_escapes_braces() {
+        let tokens = tokenize_template("{{literal}} {bin}");
+        assert_eq!(tag_names(&tokens), vec!["bin"]);
+        match &tokens[0] {
+            TemplateToken::Literal(lit) => assert_eq!(lit, "{literal} "),
+            TemplateToken::Tag { .. } => panic!("expected a literal token"),
+        }
+    }
+
+    #[test]
+    fn tokenize_template_unterminated_tag_is_literal() {
+        let tokens = tokenize_template("before {unterminated");
+        assert_eq!(tokens.len(), 1);
+        match &tokens[0] {
+            TemplateToken::Literal(lit) => assert_eq!(lit, "before {unterminated"),
+            TemplateToken::Tag { .. } => panic!("expected a literal token"),
+        }
+    }
+
+    #[test]
+    fn tokenize_template_parses_tag_params() {
+        let tokens = tokenize_template("{options:heading=true,indent=2}");
+        match &tokens[0] {
+            TemplateToken::Tag { name, params } => {
+                assert_eq!(*name, "options");
+                assert_eq!(params, &[("heading", "true"), ("indent", "2")]);
+            }
+            TemplateToken::Literal(_) => panic!("expected a tag token"),
+        }
+    }
+
+    #[test]
+    fn tokenize_template_param_without_value() {
+        let tokens = tokenize_template("{arg:NAME}");
+        match &tokens[0] {
+            TemplateToken::Tag { name, params } => {
+                assert_eq!(*name, "arg");
+                assert_eq!(params, &[("NAME", "")]);
+            }
+            TemplateToken::Literal(_) => panic!("expected a tag token"),
+        }
+    }
 }
\ No newline at end of file